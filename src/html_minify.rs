@@ -0,0 +1,270 @@
+/// Elements whose contents are whitespace-sensitive and must be passed
+/// through untouched.
+const PRESERVE_TAGS: [&str; 5] = ["pre", "code", "textarea", "script", "style"];
+
+/// Elements rendered as blocks by the HTML spec. Whitespace sitting
+/// between two tags can only be elided entirely when both sides are
+/// block-level — a browser renders no visible gap there anyway. Between
+/// inline elements (`<a>`, `<span>`, `<b>`, ...) the same whitespace is a
+/// visible space, so it must be collapsed to one space, not dropped.
+const BLOCK_TAGS: [&str; 22] = [
+    "html", "head", "body", "div", "p", "section", "article", "header",
+    "footer", "nav", "main", "aside", "ul", "ol", "li", "table", "tr",
+    "form", "blockquote", "pre", "script", "style",
+];
+
+fn is_block_level(name: Option<&str>) -> bool {
+    match name {
+        Some(n) => BLOCK_TAGS.contains(&n),
+        None => true,
+    }
+}
+
+/// Minifies an HTML document: strips comments and collapses runs of
+/// insignificant whitespace (whitespace-only text nodes sitting between
+/// two block-level tags are dropped entirely; between inline tags, or
+/// anywhere else, they collapse to a single space), while leaving the
+/// contents of `<pre>`, `<code>`, `<textarea>`, `<script>` and `<style>`
+/// untouched.
+pub fn minify(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut preserve_stack: Vec<String> = Vec::new();
+    let mut last_was_boundary = true;
+    let mut last_tag_name: Option<String> = None;
+
+    let mut i = 0;
+    while i < html.len() {
+        let rest = &html[i..];
+
+        if let Some(open_tag) = preserve_stack.last().cloned() {
+            // Inside a preserved element, only a real closing tag for the
+            // element we're in ends the region — a `<`/`>` anywhere in its
+            // content (e.g. `a < b` in a `<script>`) must not be mistaken
+            // for a tag boundary, or we'd desync and swallow everything
+            // after it into the preserved region.
+            match find_closing_tag(rest, &open_tag) {
+                Some(len) => {
+                    output.push_str(&rest[..len]);
+                    preserve_stack.pop();
+                    i += len;
+                    last_was_boundary = true;
+                    last_tag_name = Some(open_tag);
+                }
+                None => {
+                    output.push_str(rest);
+                    break;
+                }
+            }
+            continue;
+        }
+
+        if rest.starts_with("<!--") {
+            match rest.find("-->") {
+                Some(end) => i += end + 3,
+                None => break,
+            }
+            continue;
+        }
+
+        if rest.starts_with(|c: char| c.is_whitespace()) {
+            let ws_len = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+            let next = &rest[ws_len..];
+            let next_is_tag = next.starts_with('<');
+            let next_tag_name = if next_is_tag {
+                tag_len(next).and_then(|len| tag_name(&next[..len])).map(|(name, _)| name)
+            } else {
+                None
+            };
+            let elide = last_was_boundary
+                && next_is_tag
+                && is_block_level(last_tag_name.as_deref())
+                && is_block_level(next_tag_name.as_deref());
+            if !elide {
+                output.push(' ');
+            }
+            i += ws_len;
+            last_was_boundary = next_is_tag;
+            continue;
+        }
+
+        if rest.starts_with('<') {
+            if let Some(len) = tag_len(rest) {
+                let tag_str = &rest[..len];
+                output.push_str(tag_str);
+                update_preserve_stack(tag_str, &mut preserve_stack);
+                last_tag_name = tag_name(tag_str).map(|(name, _)| name);
+                i += len;
+                last_was_boundary = true;
+                continue;
+            }
+        }
+
+        let ch = rest.chars().next().unwrap();
+        output.push(ch);
+        i += ch.len_utf8();
+        last_was_boundary = false;
+    }
+
+    output
+}
+
+/// Returns the byte length of the tag starting at the beginning of `s`
+/// (which must start with `<`), including the closing `>`, respecting
+/// quoted attribute values that may themselves contain `>`.
+fn tag_len(s: &str) -> Option<usize> {
+    let mut in_single = false;
+    let mut in_double = false;
+
+    for (idx, ch) in s.char_indices().skip(1) {
+        match ch {
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '>' if !in_single && !in_double => return Some(idx + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Finds the real closing tag for `tag_name` (e.g. `</script>`) in `s`,
+/// ignoring any other `<`/`>` that appear before it (they're just content,
+/// not markup, while we're inside a preserved element). Returns the byte
+/// length of everything up to and including that closing tag.
+fn find_closing_tag(s: &str, tag_name: &str) -> Option<usize> {
+    let lower = s.to_lowercase();
+    let needle = format!("</{}", tag_name.to_lowercase());
+
+    let mut search_from = 0;
+    while let Some(found) = lower[search_from..].find(&needle) {
+        let start = search_from + found;
+        let after_name = start + needle.len();
+
+        let boundary_ok = s[after_name..]
+            .chars()
+            .next()
+            .map(|c| c.is_whitespace() || c == '>')
+            .unwrap_or(false);
+
+        if boundary_ok {
+            if let Some(len) = tag_len(&s[start..]) {
+                return Some(start + len);
+            }
+        }
+
+        search_from = after_name;
+    }
+
+    None
+}
+
+/// Extracts `(lowercased tag name, is_closing_tag)` from a full tag string
+/// like `<div class="a">`, `</div>` or `<br/>`.
+fn tag_name(tag: &str) -> Option<(String, bool)> {
+    let inner = tag.trim_start_matches('<').trim_end_matches('>').trim_end_matches('/');
+    let is_closing = inner.starts_with('/');
+    let inner = inner.trim_start_matches('/');
+
+    let name: String = inner
+        .chars()
+        .take_while(|c| !c.is_whitespace())
+        .collect();
+
+    if name.is_empty() {
+        None
+    } else {
+        Some((name.to_lowercase(), is_closing))
+    }
+}
+
+fn update_preserve_stack(tag: &str, stack: &mut Vec<String>) {
+    let (name, is_closing) = match tag_name(tag) {
+        Some(parsed) => parsed,
+        None => return,
+    };
+
+    if !PRESERVE_TAGS.contains(&name.as_str()) {
+        return;
+    }
+
+    if is_closing {
+        if stack.last() == Some(&name) {
+            stack.pop();
+        }
+    } else {
+        stack.push(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::minify;
+
+    #[test]
+    fn collapses_whitespace_between_tags() {
+        let input = "<div>\n    <p>Hello</p>\n\n    <p>World</p>\n</div>";
+        assert_eq!(minify(input), "<div><p>Hello</p><p>World</p></div>");
+    }
+
+    #[test]
+    fn strips_comments() {
+        let input = "<div><!-- a comment -->Hello</div>";
+        assert_eq!(minify(input), "<div>Hello</div>");
+    }
+
+    #[test]
+    fn preserves_pre_contents() {
+        let input = "<pre>\n  line one\n    line two\n</pre>";
+        assert_eq!(minify(input), input);
+    }
+
+    #[test]
+    fn preserves_code_contents() {
+        let input = "<p>See:</p><code>  x = 1;\n  y = 2;</code>";
+        assert_eq!(minify(input), "<p>See:</p><code>  x = 1;\n  y = 2;</code>");
+    }
+
+    #[test]
+    fn preserves_textarea_contents() {
+        let input = "<textarea>  keep   this    spacing  </textarea>";
+        assert_eq!(minify(input), input);
+    }
+
+    #[test]
+    fn preserves_script_and_style_contents() {
+        let input = "<script>\n  if (a   &&   b) {}\n</script><style>\n  .a   {  color: red; }\n</style>";
+        assert_eq!(minify(input), input);
+    }
+
+    #[test]
+    fn collapses_inline_whitespace_to_single_space() {
+        let input = "<p>Hello   World</p>";
+        assert_eq!(minify(input), "<p>Hello World</p>");
+    }
+
+    #[test]
+    fn comparison_operator_in_script_does_not_desync_preserve_stack() {
+        let input = "<script>\nif (a < b) { console.log(1); }\n</script><p>After   script</p>";
+        let expected = "<script>\nif (a < b) { console.log(1); }\n</script><p>After script</p>";
+        assert_eq!(minify(input), expected);
+    }
+
+    #[test]
+    fn child_combinator_in_style_does_not_desync_preserve_stack() {
+        let input = "<style>\n.a > .b {  color: red; }\n</style><p>After   style</p>";
+        let expected = "<style>\n.a > .b {  color: red; }\n</style><p>After style</p>";
+        assert_eq!(minify(input), expected);
+    }
+
+    #[test]
+    fn collapses_whitespace_between_inline_tags_to_a_single_space() {
+        let input = "<a>Home</a>\n<a>About</a>";
+        assert_eq!(minify(input), "<a>Home</a> <a>About</a>");
+    }
+
+    #[test]
+    fn still_elides_whitespace_between_block_tags() {
+        let input = "<div>\n<p>Hello</p>\n</div>";
+        assert_eq!(minify(input), "<div><p>Hello</p></div>");
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::TcpStream;
+
+/// Reads a full HTTP request off `stream`: grows the read buffer until the
+/// header block ends, then keeps reading until `Content-Length` bytes of
+/// body have arrived (or immediately, for requests with no body). Returns
+/// `None` if the connection closed before a complete request arrived.
+pub fn read_request(stream: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    let mut header_end = None;
+
+    loop {
+        if let Some(end) = header_end {
+            let content_length = parse_content_length(&buffer[..end]);
+            if buffer.len() >= end + 4 + content_length {
+                break;
+            }
+        }
+
+        let read = stream.read(&mut chunk).ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+
+        if header_end.is_none() {
+            header_end = find_subsequence(&buffer, b"\r\n\r\n");
+        }
+    }
+
+    if buffer.is_empty() {
+        None
+    } else {
+        Some(buffer)
+    }
+}
+
+fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn parse_content_length(headers: &[u8]) -> usize {
+    String::from_utf8_lossy(headers)
+        .lines()
+        .find_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            let name = parts.next()?.trim();
+            if name.eq_ignore_ascii_case("Content-Length") {
+                parts.next()?.trim().parse().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0)
+}
+
+/// Decodes a `%XX`-escaped, `+`-for-space string as used in query strings
+/// and `application/x-www-form-urlencoded` bodies.
+pub fn percent_decode(input: &str) -> String {
+    percent_decode_impl(input, true)
+}
+
+/// Decodes a `%XX`-escaped URL path segment. Unlike [`percent_decode`], `+`
+/// is left as a literal plus: that substitution is a form-encoding
+/// convention and doesn't apply to path components (e.g. `/c++/notes.txt`).
+pub fn percent_decode_path(input: &str) -> String {
+    percent_decode_impl(input, false)
+}
+
+fn percent_decode_impl(input: &str, plus_as_space: bool) -> String {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' if plus_as_space => {
+                output.push(b' ');
+                i += 1;
+            }
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        output.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        output.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+            }
+            byte => {
+                output.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// Parses an `&`-separated, `=`-delimited key/value string (a query string
+/// or a form-urlencoded body) into a map, percent-decoding each key and value.
+pub fn parse_pairs(input: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+
+    for pair in input.split('&') {
+        if pair.is_empty() {
+            continue;
+        }
+
+        let mut kv = pair.splitn(2, '=');
+        let key = kv.next().unwrap_or("");
+        let value = kv.next().unwrap_or("");
+        map.insert(percent_decode(key), percent_decode(value));
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_decode_handles_percent_escapes_and_plus_as_space() {
+        assert_eq!(percent_decode("a%20b"), "a b");
+        assert_eq!(percent_decode("a+b"), "a b");
+        assert_eq!(percent_decode("a%2Bb"), "a+b");
+    }
+
+    #[test]
+    fn percent_decode_passes_through_an_invalid_escape_literally() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("100%2"), "100%2");
+        assert_eq!(percent_decode("100%zz"), "100%zz");
+    }
+
+    #[test]
+    fn percent_decode_path_leaves_plus_as_a_literal_character() {
+        assert_eq!(percent_decode_path("/c%2B%2B/notes.txt"), "/c++/notes.txt");
+        assert_eq!(percent_decode_path("/a+b"), "/a+b");
+    }
+
+    #[test]
+    fn parse_pairs_decodes_keys_and_values() {
+        let parsed = parse_pairs("name=John+Doe&city=New%20York");
+        assert_eq!(parsed.get("name").map(String::as_str), Some("John Doe"));
+        assert_eq!(parsed.get("city").map(String::as_str), Some("New York"));
+    }
+
+    #[test]
+    fn parse_pairs_skips_empty_segments_and_handles_missing_values() {
+        let parsed = parse_pairs("a=1&&b=");
+        assert_eq!(parsed.get("a").map(String::as_str), Some("1"));
+        assert_eq!(parsed.get("b").map(String::as_str), Some(""));
+        assert_eq!(parsed.len(), 2);
+    }
+
+    #[test]
+    fn parse_pairs_on_empty_input_is_empty() {
+        assert!(parse_pairs("").is_empty());
+    }
+
+    #[test]
+    fn parse_content_length_finds_the_header_case_insensitively() {
+        let headers = b"GET / HTTP/1.1\r\nHost: example.com\r\ncontent-length: 42\r\n";
+        assert_eq!(parse_content_length(headers), 42);
+    }
+
+    #[test]
+    fn parse_content_length_defaults_to_zero_when_absent() {
+        let headers = b"GET / HTTP/1.1\r\nHost: example.com\r\n";
+        assert_eq!(parse_content_length(headers), 0);
+    }
+
+    #[test]
+    fn find_subsequence_locates_the_header_terminator() {
+        let buffer = b"GET / HTTP/1.1\r\nHost: x\r\n\r\nbody";
+        assert_eq!(find_subsequence(buffer, b"\r\n\r\n"), Some(23));
+        assert_eq!(find_subsequence(b"no terminator here", b"\r\n\r\n"), None);
+    }
+}
@@ -1,15 +1,38 @@
-use std::collections::{HashMap};
+mod directory_index;
+mod html_minify;
+mod http_request;
+mod mime_types;
+mod static_scan;
+mod template_engine;
+#[cfg(test)]
+mod test_support;
+
+use std::collections::{HashMap, HashSet};
 use std::net::TcpStream;
-use std::io::{Read, Write};
-use std::sync::{Arc, Mutex};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
+use std::time::Duration;
 use tera::{Context, Tera};
 use std::fs;
 use chrono::{Utc, Datelike};
 
+use mime_types::MimeTypes;
+
 pub struct Server {
     routes: HashMap<String, Arc<Mutex<dyn Fn(&str, HashMap<String, String>, &str, HashMap<String, String>) -> Result<String, String> + Send + Sync>>>,
-    static_routes: HashMap<String, String>,
+    static_routes: Arc<Mutex<HashMap<String, String>>>,
+    /// Keys in `static_routes` that came from the directory scan (as
+    /// opposed to a direct `static_route` registration), so a rescan can
+    /// replace/remove only what it previously contributed.
+    scanned_static_keys: Arc<Mutex<HashSet<String>>>,
+    static_root: Option<String>,
+    static_scan_interval: Duration,
+    directory_listing: bool,
+    minify_html: bool,
+    mime_types: Arc<MimeTypes>,
+    templates: Arc<RwLock<Tera>>,
 }
 
 impl Clone for Server {
@@ -17,6 +40,13 @@ impl Clone for Server {
         Server {
             routes: self.routes.clone(),
             static_routes: self.static_routes.clone(),
+            scanned_static_keys: self.scanned_static_keys.clone(),
+            static_root: self.static_root.clone(),
+            static_scan_interval: self.static_scan_interval,
+            directory_listing: self.directory_listing,
+            minify_html: self.minify_html,
+            mime_types: self.mime_types.clone(),
+            templates: self.templates.clone(),
         }
     }
 }
@@ -25,10 +55,23 @@ impl Server {
     pub fn new() -> Self {
         Server {
             routes: HashMap::new(),
-            static_routes: HashMap::new(),
+            static_routes: Arc::new(Mutex::new(HashMap::new())),
+            scanned_static_keys: Arc::new(Mutex::new(HashSet::new())),
+            static_root: None,
+            static_scan_interval: Duration::from_secs(5),
+            directory_listing: false,
+            minify_html: false,
+            mime_types: Arc::new(MimeTypes::load()),
+            templates: template_engine::load_and_watch("templates"),
         }
     }
 
+    /// Sets how often the background scanner re-walks the static tree to
+    /// pick up added/removed/changed files without a server restart.
+    pub fn set_static_scan_interval(&mut self, interval: Duration) {
+        self.static_scan_interval = interval;
+    }
+
     pub fn route<F>(&mut self, path: &str, func: F)
     where
         F: Fn(&str, HashMap<String, String>, &str, HashMap<String, String>) -> Result<String, String> + 'static + Send + Sync,
@@ -37,7 +80,28 @@ impl Server {
     }
 
     pub fn static_route(&mut self, path: &str, content: &str) {
-        self.static_routes.insert(path.to_string(), content.to_string());
+        if let Ok(mut static_routes) = self.static_routes.lock() {
+            static_routes.insert(path.to_string(), content.to_string());
+        }
+    }
+
+    /// Renders a template by name against the server's persistent, hot-reloading
+    /// `Tera` instance, so route handlers get `{% extends %}`/`{% include %}`
+    /// support without re-reading `templates/` from disk on every request.
+    pub fn render(&self, template_name: &str, context: &HashMap<&str, &str>) -> Result<String, String> {
+        let mut ctx = Context::new();
+        for (key, val) in context {
+            ctx.insert(*key, val);
+        }
+
+        let tera = self.templates.read().map_err(|_| "template store lock poisoned".to_string())?;
+        let rendered = tera.render(template_name, &ctx).map_err(|e| e.to_string())?;
+
+        if self.minify_html {
+            Ok(html_minify::minify(&rendered))
+        } else {
+            Ok(rendered)
+        }
     }
 
     pub fn add_route_to_sitemap(&self, path: &str, lastmod: bool, changefreq: &str, priority: f32, base_url: &str) {
@@ -81,7 +145,8 @@ impl Server {
 
             let current_date = Utc::now().format("%Y-%m-%d").to_string();
 
-            for (path, _) in &self.static_routes {
+            let static_routes = self.static_routes.lock().map(|routes| routes.clone()).unwrap_or_default();
+            for path in static_routes.keys() {
                 let full_url = format!("{}{}", base_url, path);
 
                 let url_to_check = format!("<loc>{}</loc>", full_url);
@@ -108,40 +173,107 @@ impl Server {
         }
     }
 
+    /// Recursively walks `static_folder`, registering a static route for
+    /// every file found so nested paths like `/assets/css/app.css` resolve
+    /// the same way top-level ones do. A background thread keeps rescanning
+    /// the tree afterwards; see `set_static_scan_interval`.
     pub fn load_static_files(&mut self, static_folder: &str) {
-        if let Ok(entries) = fs::read_dir(static_folder) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_file() {
-                        if let Some(file_name) = path.file_name() {
-                            if let Some(file_str) = file_name.to_str() {
-                                let route = format!("/{}", file_str);
-                                self.static_route(&route, &path.to_string_lossy());
-                            }
-                        }
-                    }
-                }
-            }
+        self.static_root = Some(static_folder.to_string());
+        let fresh = static_scan::scan(static_folder, static_folder);
+        if let (Ok(mut static_routes), Ok(mut scanned_keys)) =
+            (self.static_routes.lock(), self.scanned_static_keys.lock())
+        {
+            *scanned_keys = static_scan::merge_scanned(&mut static_routes, &scanned_keys, fresh);
         }
-    }    
+    }
 
-    fn serve_static(&self, path: &str) -> Option<String> {
-        if let Some(file_path) = self.static_routes.get(path) {
-            if let Ok(content) = fs::read_to_string(&file_path) {
-                let response = format!(
-                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
-                    content.len(),
-                    content
-                );
-                return Some(response);
-            }
+    /// Enables or disables generating an HTML directory listing for requests
+    /// that resolve to a directory under the static root instead of a file.
+    pub fn enable_directory_listing(&mut self, enabled: bool) {
+        self.directory_listing = enabled;
+    }
+
+    /// Enables or disables minifying rendered templates and served `.html`
+    /// files, trading readability for smaller response payloads.
+    pub fn enable_html_minification(&mut self, enabled: bool) {
+        self.minify_html = enabled;
+    }
+
+    fn serve_directory_index(&self, path: &str) -> Option<Vec<u8>> {
+        if !self.directory_listing {
+            return None;
         }
-        None
-    }    
+
+        // A manually registered `static_route(path, ...)` takes precedence
+        // over autoindexing, even if `path` also happens to resolve to a
+        // real directory under the static root — otherwise a route like
+        // `static_route("/api", json_blob)` would silently start serving a
+        // directory listing instead of its registered content whenever a
+        // `static/api/` folder exists on disk.
+        let has_manual_override = self
+            .static_routes
+            .lock()
+            .ok()
+            .map(|routes| routes.contains_key(path))
+            .unwrap_or(false)
+            && !self
+                .scanned_static_keys
+                .lock()
+                .ok()
+                .map(|keys| keys.contains(path))
+                .unwrap_or(false);
+        if has_manual_override {
+            return None;
+        }
+
+        let root = self.static_root.as_ref()?;
+        let dir_path = Path::new(root).join(path.trim_start_matches('/'));
+        if !dir_path.is_dir() {
+            return None;
+        }
+
+        // Reject any request path that escapes the static root (e.g. via `..`
+        // segments) once symlinks/`..` are resolved, before touching the filesystem.
+        let canonical_root = fs::canonicalize(root).ok()?;
+        let canonical_dir = fs::canonicalize(&dir_path).ok()?;
+        if !canonical_dir.starts_with(&canonical_root) {
+            return None;
+        }
+
+        let html = directory_index::render(&canonical_dir, path).ok()?;
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\n\r\n",
+            html.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(html.as_bytes());
+        Some(response)
+    }
+
+    fn serve_static(&self, path: &str) -> Option<Vec<u8>> {
+        let file_path = self.static_routes.lock().ok()?.get(path)?.clone();
+        let mut content = fs::read(&file_path).ok()?;
+        let mime_type = self.mime_types.lookup(&file_path);
+
+        if self.minify_html && file_path.ends_with(".html") {
+            content = match String::from_utf8(content) {
+                Ok(text) => html_minify::minify(&text).into_bytes(),
+                Err(err) => err.into_bytes(),
+            };
+        }
+
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            mime_type,
+            content.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&content);
+        Some(response)
+    }
 
     fn handle_static(&self, path: &str) -> Option<String> {
-        if let Some(content) = self.static_routes.get(path) {
+        if let Some(content) = self.static_routes.lock().ok()?.get(path) {
             Some(content.clone())
         } else {
             None
@@ -171,49 +303,55 @@ impl Server {
     }
 
     fn handle_connection(&self, mut stream: TcpStream) {
-        let mut buffer = [0; 1024];
-        stream.read(&mut buffer).unwrap();
-    
-        let request = String::from_utf8_lossy(&buffer[..]);
-        let request_parts: Vec<&str> = request.split("\r\n\r\n").collect();
-    
-        let path_params: Vec<&str> = request_parts[0].split_whitespace().collect();
-        let path = path_params[1].split('?').next().unwrap_or("");
-    
+        let raw_request = match http_request::read_request(&mut stream) {
+            Some(raw_request) => raw_request,
+            None => return,
+        };
+
+        let request = String::from_utf8_lossy(&raw_request);
+        let mut request_parts = request.splitn(2, "\r\n\r\n");
+        let head = request_parts.next().unwrap_or("");
+        let body = request_parts.next().unwrap_or("");
+
+        let path_params: Vec<&str> = head.split_whitespace().collect();
+        if path_params.len() < 2 {
+            return;
+        }
+        // The raw path arrives percent-escaped (e.g. a space as `%20`), but
+        // static_routes/static_root are keyed by literal on-disk names, so
+        // it must be decoded before any route or filesystem lookup.
+        let path = http_request::percent_decode_path(path_params[1].split('?').next().unwrap_or(""));
+        let path = path.as_str();
+
         if let Some(content) = self.serve_static(path) {
-            stream.write_all(content.as_bytes()).unwrap();
+            stream.write_all(&content).unwrap();
             stream.flush().unwrap();
             return;
         }
-    
-        let method = path_params[0];
-        let mut params = HashMap::new();
-        let mut data = HashMap::new();
-    
-        if let Some(query_params) = path_params[1].splitn(2, '?').nth(1) {
-            let payload_parts: Vec<&str> = query_params.split("&").collect();
-            for part in payload_parts {
-                let kv: Vec<&str> = part.split("=").collect();
-                if kv.len() == 2 {
-                    params.insert(kv[0].to_string(), kv[1].to_string());
-                }
-            }
-        }
-    
-        let body_params: Vec<&str> = request_parts[1].split('&').collect();
-        for part in body_params {
-            let kv: Vec<&str> = part.split("=").collect();
-            if kv.len() == 2 {
-                data.insert(kv[0].to_string(), kv[1].to_string());
-            }
+
+        if let Some(content) = self.serve_directory_index(path) {
+            stream.write_all(&content).unwrap();
+            stream.flush().unwrap();
+            return;
         }
-    
-        let response = match self.static_routes.get(path) {
-            Some(content) => Ok(format!("{}", content)),
+
+        let method = path_params[0];
+
+        let query_params = path_params[1]
+            .split_once('?')
+            .map(|(_, query)| http_request::parse_pairs(query))
+            .unwrap_or_default();
+
+        let data = http_request::parse_pairs(body);
+
+        let matched_static_route = self.static_routes.lock().ok().and_then(|routes| routes.get(path).cloned());
+        let response = match matched_static_route {
+            Some(content) => Ok(content),
             None => match self.routes.iter().find_map(|(route, handler)| {
-                if let Some(params) = self.path_matches_route(path, route) {
+                if let Some(route_params) = self.path_matches_route(path, route) {
                     let handler = handler.lock().unwrap();
-                    let cloned_params = params.clone();
+                    let mut cloned_params = query_params.clone();
+                    cloned_params.extend(route_params);
                     let cloned_data = data.clone();
                     return Some(handler(path, cloned_params, method, cloned_data).map(|res| {
                         res
@@ -241,7 +379,16 @@ impl Server {
 
     pub fn run(mut self, ip: &str, port: &str) {
         self.load_static_files("static");
-        
+
+        if let Some(static_root) = self.static_root.clone() {
+            static_scan::watch(
+                static_root,
+                self.static_routes.clone(),
+                self.scanned_static_keys.clone(),
+                self.static_scan_interval,
+            );
+        }
+
         let listener = std::net::TcpListener::bind(format!("{}:{}", ip, port)).unwrap();
 
         for stream in listener.incoming() {
@@ -255,10 +402,17 @@ impl Server {
     }
 }
 
+/// Stateless one-off rendering helpers, kept for callers that don't go
+/// through a `Server`. Each call reparses its template from scratch; prefer
+/// `Server::render` where a `Server` is available, since it reuses the
+/// persistent, hot-reloading `Tera` instance instead.
 pub struct TemplateEngine;
 
 impl TemplateEngine {
-    pub fn render(template: &str, context: &HashMap<&str, &str>) -> String {
+    /// Renders `template` against `context`. When `minify` is `true` the
+    /// result is passed through [`html_minify::minify`] before returning,
+    /// matching the opt-in minification `Server::render` applies.
+    pub fn render(template: &str, context: &HashMap<&str, &str>, minify: bool) -> String {
         let mut tera = Tera::default();
         tera.add_raw_template("template", template).unwrap();
 
@@ -267,10 +421,22 @@ impl TemplateEngine {
             ctx.insert(*key, val);
         }
 
-        tera.render("template", &ctx).unwrap()
+        let rendered = tera.render("template", &ctx).unwrap();
+        if minify {
+            html_minify::minify(&rendered)
+        } else {
+            rendered
+        }
     }
 
-    pub fn render_template(template_name: &str, context: &HashMap<&str, &str>) -> Result<String, String> {
+    /// Renders `templates/{template_name}` against `context`. When `minify`
+    /// is `true` the result is passed through [`html_minify::minify`] before
+    /// returning, matching the opt-in minification `Server::render` applies.
+    pub fn render_template(
+        template_name: &str,
+        context: &HashMap<&str, &str>,
+        minify: bool,
+    ) -> Result<String, String> {
         let file_content = match std::fs::read_to_string(format!("templates/{}", template_name)) {
             Ok(content) => content,
             Err(_) => return Err("Template file not found".to_string()),
@@ -284,6 +450,116 @@ impl TemplateEngine {
             ctx.insert(*key, val);
         }
 
-        tera.render("template", &ctx).map_err(|e| e.to_string())
+        let rendered = tera.render("template", &ctx).map_err(|e| e.to_string())?;
+        if minify {
+            Ok(html_minify::minify(&rendered))
+        } else {
+            Ok(rendered)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn directory_index_rejects_paths_that_escape_the_static_root() {
+        let root = temp_dir("root");
+        let outside = temp_dir("outside");
+        fs::write(outside.join("secret.txt"), b"top secret").unwrap();
+
+        let mut server = Server::new();
+        server.static_root = Some(root.to_string_lossy().to_string());
+        server.directory_listing = true;
+
+        let outside_name = outside.file_name().unwrap().to_string_lossy().to_string();
+        let escaping_path = format!("/../{}", outside_name);
+
+        assert!(server.serve_directory_index(&escaping_path).is_none());
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&outside).ok();
+    }
+
+    #[test]
+    fn static_route_survives_load_static_files_and_a_rescan() {
+        let root = temp_dir("survives-scan");
+        fs::write(root.join("app.js"), b"console.log(1)").unwrap();
+
+        let mut server = Server::new();
+        server.static_route("/manual", "registered by hand");
+
+        server.load_static_files(root.to_str().unwrap());
+        assert_eq!(
+            server.static_routes.lock().unwrap().get("/manual").map(String::as_str),
+            Some("registered by hand"),
+            "static_route entry should survive the initial scan"
+        );
+        assert!(server.static_routes.lock().unwrap().contains_key("/app.js"));
+
+        // A rescan (as the background watcher performs) must also leave
+        // manually registered routes in place, not just the first scan.
+        server.load_static_files(root.to_str().unwrap());
+        assert_eq!(
+            server.static_routes.lock().unwrap().get("/manual").map(String::as_str),
+            Some("registered by hand"),
+            "static_route entry should survive a rescan"
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn directory_index_does_not_shadow_a_manually_registered_static_route() {
+        let root = temp_dir("manual-vs-autoindex");
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(root.join("api/real.txt"), b"on-disk file").unwrap();
+
+        let mut server = Server::new();
+        server.directory_listing = true;
+        server.static_root = Some(root.to_string_lossy().to_string());
+        server.static_route("/api", "literal content, not a file path");
+
+        // The registered route is not produced by the scan, so it must
+        // shadow the directory autoindex for the colliding path.
+        assert!(server.serve_directory_index("/api").is_none());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn template_engine_render_minifies_only_when_asked() {
+        let context = HashMap::new();
+        let template = "<div>\n    <p>Hi</p>\n</div>";
+
+        assert_eq!(TemplateEngine::render(template, &context, false), template);
+        assert_eq!(
+            TemplateEngine::render(template, &context, true),
+            "<div><p>Hi</p></div>"
+        );
+    }
+
+    #[test]
+    fn template_engine_render_template_minifies_only_when_asked() {
+        // `render_template` always reads from a `templates/` directory
+        // relative to the process's current directory, so (unlike `build`
+        // in template_engine.rs) this can't be pointed at an isolated temp
+        // dir without changing the global cwd; use a uniquely named file
+        // under the real `templates/` dir instead to avoid cross-test races.
+        fs::create_dir_all("templates").unwrap();
+        let name = format!("sandy-lib-test-render-template-{}.html", std::process::id());
+        fs::write(format!("templates/{}", name), "<div>\n    <p>Hi</p>\n</div>").unwrap();
+
+        let context = HashMap::new();
+        let plain = TemplateEngine::render_template(&name, &context, false).unwrap();
+        let minified = TemplateEngine::render_template(&name, &context, true).unwrap();
+
+        fs::remove_file(format!("templates/{}", name)).ok();
+
+        assert_eq!(plain, "<div>\n    <p>Hi</p>\n</div>");
+        assert_eq!(minified, "<div><p>Hi</p></div>");
     }
 }
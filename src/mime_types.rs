@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Extension -> MIME type lookup, seeded from `/etc/mime.types` on startup
+/// and backed by a small built-in table for the common web asset types that
+/// may be missing (or that `/etc/mime.types` doesn't exist at all, e.g. in
+/// minimal containers).
+pub struct MimeTypes {
+    parsed: HashMap<String, String>,
+}
+
+impl MimeTypes {
+    pub fn load() -> Self {
+        let parsed = match fs::read_to_string("/etc/mime.types") {
+            Ok(content) => Self::parse(&content),
+            Err(_) => HashMap::new(),
+        };
+
+        MimeTypes { parsed }
+    }
+
+    /// Parses the `type ext1 ext2 ...` line format used by `/etc/mime.types`,
+    /// skipping blank lines and `#` comments.
+    fn parse(content: &str) -> HashMap<String, String> {
+        let mut parsed = HashMap::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            if let Some(mime_type) = fields.next() {
+                for ext in fields {
+                    parsed.insert(ext.to_string(), mime_type.to_string());
+                }
+            }
+        }
+
+        parsed
+    }
+
+    fn builtin(ext: &str) -> Option<&'static str> {
+        Some(match ext {
+            "html" | "htm" => "text/html",
+            "css" => "text/css",
+            "js" => "application/javascript",
+            "json" => "application/json",
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "svg" => "image/svg+xml",
+            "ico" => "image/x-icon",
+            "txt" => "text/plain",
+            "xml" => "application/xml",
+            "pdf" => "application/pdf",
+            "woff" => "font/woff",
+            "woff2" => "font/woff2",
+            _ => return None,
+        })
+    }
+
+    /// Looks up the MIME type for a file path based on its extension,
+    /// falling back to `application/octet-stream` when unknown.
+    pub fn lookup(&self, path: &str) -> String {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        if let Some(mime_type) = self.parsed.get(&ext) {
+            return mime_type.clone();
+        }
+
+        Self::builtin(&ext)
+            .map(str::to_string)
+            .unwrap_or_else(|| "application/octet-stream".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        let content = "# a comment\ntext/html html htm\n\napplication/json json\n  # indented comment\n";
+        let parsed = MimeTypes::parse(content);
+
+        assert_eq!(parsed.get("html").map(String::as_str), Some("text/html"));
+        assert_eq!(parsed.get("htm").map(String::as_str), Some("text/html"));
+        assert_eq!(parsed.get("json").map(String::as_str), Some("application/json"));
+        assert_eq!(parsed.len(), 3);
+    }
+
+    #[test]
+    fn lookup_falls_back_to_the_builtin_table_when_unparsed() {
+        let mime = MimeTypes { parsed: HashMap::new() };
+        assert_eq!(mime.lookup("style.css"), "text/css");
+        assert_eq!(mime.lookup("logo.svg"), "image/svg+xml");
+    }
+
+    #[test]
+    fn lookup_prefers_a_parsed_entry_over_the_builtin_table() {
+        let mut parsed = HashMap::new();
+        parsed.insert("css".to_string(), "text/x-custom-css".to_string());
+        let mime = MimeTypes { parsed };
+
+        assert_eq!(mime.lookup("style.css"), "text/x-custom-css");
+    }
+
+    #[test]
+    fn lookup_defaults_to_octet_stream_for_an_unknown_extension() {
+        let mime = MimeTypes { parsed: HashMap::new() };
+        assert_eq!(mime.lookup("archive.unknownext"), "application/octet-stream");
+    }
+
+    #[test]
+    fn lookup_does_not_mistake_an_extensionless_name_for_its_extension() {
+        let mime = MimeTypes { parsed: HashMap::new() };
+        assert_eq!(mime.lookup("html"), "application/octet-stream");
+        assert_eq!(mime.lookup("css"), "application/octet-stream");
+    }
+}
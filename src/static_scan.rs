@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Recursively walks `dir` (a subtree of `static_root`) building a
+/// route -> file-path map, the same way `Server::load_static_files` does on
+/// startup.
+pub fn scan(static_root: &str, dir: &str) -> HashMap<String, String> {
+    let mut routes = HashMap::new();
+    scan_into(static_root, dir, &mut routes);
+    routes
+}
+
+fn scan_into(static_root: &str, dir: &str, routes: &mut HashMap<String, String>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if let Some(dir_str) = path.to_str() {
+                scan_into(static_root, dir_str, routes);
+            }
+        } else if path.is_file() {
+            if let Ok(relative) = path.strip_prefix(static_root) {
+                let route = format!("/{}", relative.to_string_lossy().replace('\\', "/"));
+                routes.insert(route, path.to_string_lossy().to_string());
+            }
+        }
+    }
+}
+
+/// Merges a freshly scanned route map into `static_routes` without
+/// disturbing routes registered by `Server::static_route` (or any other
+/// caller). `previously_scanned` is the key set this function returned the
+/// last time it ran; any of those keys missing from `fresh` came from a
+/// file that's since been removed/renamed on disk, so they're dropped —
+/// everything else in `static_routes` (i.e. not in `previously_scanned`)
+/// is left untouched. Returns the key set to pass back in as
+/// `previously_scanned` on the next scan.
+pub fn merge_scanned(
+    static_routes: &mut HashMap<String, String>,
+    previously_scanned: &HashSet<String>,
+    fresh: HashMap<String, String>,
+) -> HashSet<String> {
+    for stale in previously_scanned.difference(&fresh.keys().cloned().collect()) {
+        static_routes.remove(stale);
+    }
+
+    let scanned_keys = fresh.keys().cloned().collect();
+    static_routes.extend(fresh);
+    scanned_keys
+}
+
+/// Returns the most recent modification time found anywhere under `dir`.
+fn latest_mtime(dir: &Path) -> SystemTime {
+    let mut latest = SystemTime::UNIX_EPOCH;
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return latest,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if let Ok(metadata) = entry.metadata() {
+            if let Ok(modified) = metadata.modified() {
+                latest = latest.max(modified);
+            }
+        }
+
+        if path.is_dir() {
+            latest = latest.max(latest_mtime(&path));
+        }
+    }
+
+    latest
+}
+
+/// Spawns a background thread modeled on `static-site-server-rs`'s
+/// `ServerRoutesScan`: on each tick of `interval` it compares the static
+/// tree's most recent modification time against the last-seen one, and when
+/// it changed, re-walks the tree and atomically merges a freshly built route
+/// map into `routes` so concurrent `handle_connection` threads always see a
+/// consistent snapshot. Only routes this scan previously produced are ever
+/// replaced/removed — routes registered directly via `Server::static_route`
+/// survive every rescan.
+pub fn watch(
+    static_root: String,
+    routes: Arc<Mutex<HashMap<String, String>>>,
+    scanned_keys: Arc<Mutex<HashSet<String>>>,
+    interval: Duration,
+) {
+    thread::spawn(move || {
+        let mut last_modified = latest_mtime(Path::new(&static_root));
+
+        loop {
+            thread::sleep(interval);
+
+            let modified = latest_mtime(Path::new(&static_root));
+            if modified <= last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            let fresh = scan(&static_root, &static_root);
+            if let (Ok(mut routes), Ok(mut scanned_keys)) = (routes.lock(), scanned_keys.lock()) {
+                *scanned_keys = merge_scanned(&mut routes, &scanned_keys, fresh);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::collections::HashSet;
+    use std::time::Duration as StdDuration;
+
+    #[test]
+    fn scan_finds_nested_files() {
+        let root = temp_dir("scan-nested");
+        fs::create_dir_all(root.join("assets/css")).unwrap();
+        fs::write(root.join("index.html"), b"hi").unwrap();
+        fs::write(root.join("assets/css/app.css"), b"body{}").unwrap();
+
+        let root_str = root.to_str().unwrap();
+        let routes = scan(root_str, root_str);
+
+        assert_eq!(routes.get("/index.html"), Some(&format!("{}/index.html", root_str)));
+        assert_eq!(
+            routes.get("/assets/css/app.css"),
+            Some(&format!("{}/assets/css/app.css", root_str))
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn latest_mtime_picks_up_a_touched_file() {
+        let root = temp_dir("mtime");
+        fs::write(root.join("a.txt"), b"one").unwrap();
+        let before = latest_mtime(&root);
+
+        thread::sleep(StdDuration::from_millis(20));
+        fs::write(root.join("b.txt"), b"two").unwrap();
+        let after = latest_mtime(&root);
+
+        assert!(after > before, "expected {after:?} to be newer than {before:?}");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn merge_scanned_preserves_unrelated_routes_and_drops_stale_scanned_ones() {
+        let mut static_routes = HashMap::new();
+        static_routes.insert("/manual".to_string(), "registered by hand".to_string());
+
+        let mut first_scan = HashMap::new();
+        first_scan.insert("/a.txt".to_string(), "/static/a.txt".to_string());
+        first_scan.insert("/b.txt".to_string(), "/static/b.txt".to_string());
+        let scanned_keys = merge_scanned(&mut static_routes, &HashSet::new(), first_scan);
+
+        assert_eq!(static_routes.get("/manual"), Some(&"registered by hand".to_string()));
+        assert!(static_routes.contains_key("/a.txt"));
+        assert!(static_routes.contains_key("/b.txt"));
+
+        // b.txt was removed from disk; a rescan should drop it without
+        // touching the manually registered route.
+        let mut second_scan = HashMap::new();
+        second_scan.insert("/a.txt".to_string(), "/static/a.txt".to_string());
+        merge_scanned(&mut static_routes, &scanned_keys, second_scan);
+
+        assert_eq!(static_routes.get("/manual"), Some(&"registered by hand".to_string()));
+        assert!(static_routes.contains_key("/a.txt"));
+        assert!(!static_routes.contains_key("/b.txt"));
+    }
+}
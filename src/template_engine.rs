@@ -0,0 +1,104 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use hotwatch::{EventKind, Hotwatch};
+use tera::Tera;
+
+/// Loads every template under `templates_dir` once via Tera's glob support
+/// (so `{% extends %}` / `{% include %}` resolve across files) and spawns a
+/// background thread that recompiles the set whenever a file under the
+/// directory changes, swapping the result into the shared `Tera` instance.
+pub fn load_and_watch(templates_dir: &str) -> Arc<RwLock<Tera>> {
+    let tera = Arc::new(RwLock::new(build(templates_dir)));
+
+    let watched = tera.clone();
+    let dir = templates_dir.to_string();
+    thread::spawn(move || watch(&dir, watched));
+
+    tera
+}
+
+fn build(templates_dir: &str) -> Tera {
+    let glob = format!("{}/**/*", templates_dir);
+    Tera::new(&glob).unwrap_or_else(|e| {
+        eprintln!("Failed to load templates from {}: {}", templates_dir, e);
+        Tera::default()
+    })
+}
+
+fn watch(templates_dir: &str, tera: Arc<RwLock<Tera>>) {
+    let mut hotwatch = match Hotwatch::new() {
+        Ok(hotwatch) => hotwatch,
+        Err(e) => {
+            eprintln!("Failed to start template watcher: {}", e);
+            return;
+        }
+    };
+
+    let dir = templates_dir.to_string();
+    let result = hotwatch.watch(templates_dir, move |event| {
+        if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)) {
+            let reloaded = build(&dir);
+            if let Ok(mut tera) = tera.write() {
+                *tera = reloaded;
+            }
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to watch templates directory: {}", e);
+        return;
+    }
+
+    // `hotwatch` stops watching once dropped, so keep it (and this thread) alive.
+    loop {
+        thread::sleep(Duration::from_secs(60));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::fs;
+    use tera::Context;
+
+    #[test]
+    fn build_loads_every_template_under_the_glob() {
+        let dir = temp_dir("glob");
+        fs::write(dir.join("hello.html"), "Hello, {{ name }}!").unwrap();
+
+        let tera = build(dir.to_str().unwrap());
+        let mut context = Context::new();
+        context.insert("name", "World");
+
+        assert_eq!(tera.render("hello.html", &context).unwrap(), "Hello, World!");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn build_resolves_extends_and_include_across_files() {
+        let dir = temp_dir("extends");
+        fs::write(dir.join("base.html"), "<body>{% block content %}{% endblock %}</body>").unwrap();
+        fs::write(
+            dir.join("page.html"),
+            "{% extends \"base.html\" %}{% block content %}hi{% endblock %}",
+        )
+        .unwrap();
+
+        let tera = build(dir.to_str().unwrap());
+        let context = Context::new();
+
+        assert_eq!(tera.render("page.html", &context).unwrap(), "<body>hi</body>");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn build_falls_back_to_an_empty_engine_on_a_missing_directory() {
+        let tera = build("/no/such/templates/dir/sandy-test");
+        assert!(tera.get_template_names().next().is_none());
+    }
+}
@@ -0,0 +1,14 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Creates (and empties, if it already exists) a process-unique temp
+/// directory for a test to write fixture files into. `label` should
+/// describe the scenario (e.g. `"root"`, `"outside"`) and need only be
+/// unique within the calling test.
+pub fn temp_dir(label: &str) -> PathBuf {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("sandy-test-{}-{}", label, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}